@@ -64,6 +64,32 @@ pub enum Primitive {
     GeometryPrim = ctru_sys::GPU_GEOMETRY_PRIM,
 }
 
+/// The type of an index into vertex buffer data, used for indexed drawing
+/// with [`Instance::draw_elements`](crate::Instance::draw_elements).
+///
+/// This trait is sealed and implemented only for [`u8`] and [`u16`], which are
+/// the index types supported by the GPU.
+pub trait Index: Copy + Into<u32> + private::Sealed {
+    /// The `C3D_UNSIGNED_*` constant describing this index type.
+    #[doc(hidden)]
+    const RAW_TYPE: libc::c_int;
+}
+
+impl Index for u8 {
+    const RAW_TYPE: libc::c_int = citro3d_sys::C3D_UNSIGNED_BYTE as libc::c_int;
+}
+
+impl Index for u16 {
+    const RAW_TYPE: libc::c_int = citro3d_sys::C3D_UNSIGNED_SHORT as libc::c_int;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+}
+
 impl Default for Info {
     #[doc(alias = "BufInfo_Init")]
     fn default() -> Self {
@@ -20,6 +20,8 @@ pub enum Error {
     FailedToInitialize,
     /// A size parameter was specified that cannot be converted to the proper type.
     InvalidSize,
+    /// A numeric parameter was not valid, e.g. it was NaN or infinite.
+    InvalidValue,
     /// Failed to select the given render target for drawing to.
     InvalidRenderTarget,
     /// Indicates that a reference could not be obtained because a lock is already
@@ -32,6 +34,8 @@ pub enum Error {
     /// The given memory could not be converted to a physical address for sharing
     /// with the GPU. Data should be allocated with [`ctru::linear`].
     InvalidMemoryLocation,
+    /// The given memory is not aligned as required by the GPU.
+    InvalidAlignment,
     /// The given name was not valid for the requested purpose.
     InvalidName,
     /// The requested resource could not be found.
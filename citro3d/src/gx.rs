@@ -0,0 +1,199 @@
+//! Submit GX commands (memory fills and display transfers) alongside `citro3d`'s
+//! own rendering commands.
+//!
+//! `citro3d` binds its own GX command queue when it is initialized, and relies on
+//! that queue's completion callback to keep track of frames. Submitting GX
+//! commands directly with `ctru-sys` can interfere with that bookkeeping, so
+//! this module instead goes through `citro3d`'s synchronized helpers, which
+//! wait for any queued work to finish before running the requested command.
+//!
+//! For the same reason, neither the raw `gxCmdQueue_s` nor its completion callback
+//! are exposed here.
+//!
+//! Use [`Instance::gx_queue`](crate::Instance::gx_queue) to get a [`Queue`].
+
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use crate::render::transfer;
+use crate::{Error, Instance, Result};
+
+/// A handle for submitting commands to the GX command queue used by `citro3d`.
+///
+/// Every command waits for the queue to be idle before it is submitted, and
+/// then for itself to complete, so the data borrowed by each command only needs
+/// to live as long as that call. The data cache is flushed for input buffers
+/// before each command, and invalidated for output buffers afterwards.
+#[derive(Debug)]
+pub struct Queue<'instance> {
+    _instance: PhantomData<&'instance mut Instance>,
+}
+
+/// The size of each value written by a [memory fill](Queue::memory_fill).
+#[repr(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[doc(alias = "GX_FILL_CONTROL")]
+pub enum FillWidth {
+    /// Fill with 16-bit values, e.g. for a 16-bit depth buffer or RGB565 color buffer.
+    Bits16 = ctru_sys::GX_FILL_16BIT_DEPTH as u16,
+    /// Fill with 24-bit values, e.g. for a 24-bit depth buffer or RGB8 color buffer.
+    Bits24 = ctru_sys::GX_FILL_24BIT_DEPTH as u16,
+    /// Fill with 32-bit values, e.g. for a depth/stencil buffer or RGBA8 color buffer.
+    Bits32 = ctru_sys::GX_FILL_32BIT_DEPTH as u16,
+}
+
+/// The dimensions of a buffer used in a [display transfer](Queue::display_transfer),
+/// in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dimensions {
+    /// The width of the buffer.
+    pub width: u16,
+    /// The height of the buffer.
+    pub height: u16,
+}
+
+impl Dimensions {
+    /// The number of pixels in a buffer with these dimensions. Every transfer format
+    /// uses at most 32 bits per pixel, so this many `u32`s are always enough to
+    /// hold the whole buffer.
+    fn pixels(self) -> usize {
+        usize::from(self.width) * usize::from(self.height)
+    }
+
+    fn bits(self) -> u32 {
+        // Equivalent to the `GX_BUFFER_DIM` macro
+        (u32::from(self.height) << 16) | u32::from(self.width)
+    }
+}
+
+impl<'instance> Queue<'instance> {
+    pub(crate) fn new(_instance: &'instance mut Instance) -> Self {
+        Self {
+            _instance: PhantomData,
+        }
+    }
+
+    /// Fill `buf` with the given `value`, where each element of the fill is
+    /// `width` bits wide.
+    ///
+    /// # Errors
+    ///
+    /// * if `buf` is not allocated in linear memory or VRAM
+    /// * if the start or end of `buf` is not 8-byte aligned
+    /// * if the data cache could not be flushed or invalidated
+    #[doc(alias = "C3D_SyncMemoryFill")]
+    #[doc(alias = "GX_MemoryFill")]
+    pub fn memory_fill(&mut self, buf: &mut [u32], value: u32, width: FillWidth) -> Result<()> {
+        check_gpu_accessible(buf)?;
+        check_aligned(buf)?;
+
+        // Make sure dirty cache lines aren't written back over the filled data later
+        flush_data_cache(buf)?;
+
+        let Range { start, end } = buf.as_mut_ptr_range();
+
+        unsafe {
+            citro3d_sys::C3D_SyncMemoryFill(
+                start,
+                value,
+                end,
+                ctru_sys::GX_FILL_TRIGGER as u16 | width as u16,
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+                0,
+            );
+        }
+
+        invalidate_data_cache(buf)
+    }
+
+    /// Copy `input` to `output`, converting between formats (and optionally
+    /// flipping, tiling or scaling) as described by `flags`. This is the same operation
+    /// used to copy a rendered frame to the screen.
+    ///
+    /// # Errors
+    ///
+    /// * if either buffer is not allocated in linear memory or VRAM
+    /// * if the start or end of either buffer is not 8-byte aligned
+    /// * if either buffer is too small for its dimensions
+    /// * if the data cache could not be flushed or invalidated
+    #[doc(alias = "C3D_SyncDisplayTransfer")]
+    #[doc(alias = "GX_DisplayTransfer")]
+    pub fn display_transfer(
+        &mut self,
+        input: &[u32],
+        input_dims: Dimensions,
+        output: &mut [u32],
+        output_dims: Dimensions,
+        flags: transfer::Flags,
+    ) -> Result<()> {
+        if input.len() < input_dims.pixels() || output.len() < output_dims.pixels() {
+            return Err(Error::InvalidSize);
+        }
+
+        check_gpu_accessible(input)?;
+        check_gpu_accessible(output)?;
+        check_aligned(input)?;
+        check_aligned(output)?;
+
+        flush_data_cache(input)?;
+        flush_data_cache(output)?;
+
+        unsafe {
+            citro3d_sys::C3D_SyncDisplayTransfer(
+                // The input is never written to, but the C API takes a mutable pointer
+                input.as_ptr().cast_mut(),
+                input_dims.bits(),
+                output.as_mut_ptr(),
+                output_dims.bits(),
+                flags.bits(),
+            );
+        }
+
+        invalidate_data_cache(output)
+    }
+}
+
+/// The GPU can only access linear memory and VRAM, which both have a physical
+/// address that `osConvertVirtToPhys` can find.
+pub(crate) fn check_gpu_accessible<T>(data: &[T]) -> Result<()> {
+    if unsafe { ctru_sys::osConvertVirtToPhys(data.as_ptr().cast()) } == 0 {
+        Err(Error::InvalidMemoryLocation)
+    } else {
+        Ok(())
+    }
+}
+
+/// The GX hardware registers hold physical addresses shifted right by 3, so any
+/// buffer that doesn't start and end on an 8-byte boundary would be overrun.
+fn check_aligned<T>(data: &[T]) -> Result<()> {
+    let Range { start, end } = data.as_ptr_range();
+    if start as usize % 8 == 0 && end as usize % 8 == 0 {
+        Ok(())
+    } else {
+        Err(Error::InvalidAlignment)
+    }
+}
+
+#[doc(alias = "GSPGPU_FlushDataCache")]
+fn flush_data_cache<T>(data: &[T]) -> Result<()> {
+    let size = std::mem::size_of_val(data).try_into()?;
+    let res = unsafe { ctru_sys::GSPGPU_FlushDataCache(data.as_ptr().cast(), size) };
+    if res < 0 {
+        Err(Error::System(res))
+    } else {
+        Ok(())
+    }
+}
+
+#[doc(alias = "GSPGPU_InvalidateDataCache")]
+fn invalidate_data_cache<T>(data: &[T]) -> Result<()> {
+    let size = std::mem::size_of_val(data).try_into()?;
+    let res = unsafe { ctru_sys::GSPGPU_InvalidateDataCache(data.as_ptr().cast(), size) };
+    if res < 0 {
+        Err(Error::System(res))
+    } else {
+        Ok(())
+    }
+}
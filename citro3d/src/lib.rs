@@ -1,3 +1,4 @@
+#![feature(allocator_api)]
 #![feature(custom_test_frameworks)]
 #![test_runner(test_runner::run_gdb)]
 #![feature(doc_cfg)]
@@ -19,13 +20,16 @@
 pub mod attrib;
 pub mod buffer;
 pub mod error;
+pub mod gx;
 pub mod math;
 pub mod render;
 pub mod shader;
+pub mod terrain;
 pub mod texenv;
 pub mod uniform;
 
 use std::cell::{OnceCell, RefMut};
+use std::ffi::c_void;
 use std::fmt;
 use std::rc::Rc;
 
@@ -46,6 +50,7 @@ pub mod macros {
 #[must_use]
 pub struct Instance {
     texenvs: [OnceCell<TexEnv>; texenv::TEXENV_COUNT],
+    frame_end_hook: Option<FrameEndHook>,
     queue: Rc<RenderQueue>,
 }
 
@@ -55,6 +60,39 @@ pub struct Instance {
 /// lifetime.
 struct RenderQueue;
 
+/// A callback registered with `C3D_FrameEndHook`. Only one hook can be registered
+/// at a time, and it is unregistered when this is dropped.
+struct FrameEndHook(Box<Box<dyn FnMut()>>);
+
+impl FrameEndHook {
+    #[doc(alias = "C3D_FrameEndHook")]
+    fn new(hook: impl FnMut() + 'static) -> Self {
+        // Double-box so we can pass a thin pointer to citro3d
+        let mut hook: Box<Box<dyn FnMut()>> = Box::new(Box::new(hook));
+        let param: *mut Box<dyn FnMut()> = &mut *hook;
+
+        // SAFETY: the boxed hook lives until we unregister it in `drop`
+        unsafe {
+            citro3d_sys::C3D_FrameEndHook(Some(Self::trampoline), param.cast());
+        }
+
+        Self(hook)
+    }
+
+    unsafe extern "C" fn trampoline(param: *mut c_void) {
+        let hook = unsafe { &mut *param.cast::<Box<dyn FnMut()>>() };
+        hook();
+    }
+}
+
+impl Drop for FrameEndHook {
+    fn drop(&mut self) {
+        unsafe {
+            citro3d_sys::C3D_FrameEndHook(None, std::ptr::null_mut());
+        }
+    }
+}
+
 impl fmt::Debug for Instance {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Instance").finish_non_exhaustive()
@@ -89,6 +127,7 @@ impl Instance {
                     OnceCell::new(),
                     OnceCell::new(),
                 ],
+                frame_end_hook: None,
                 queue: Rc::new(RenderQueue),
             })
         } else {
@@ -152,6 +191,35 @@ impl Instance {
         }
     }
 
+    /// Register a function to be called at the end of every frame (i.e. during
+    /// each call to [`render_frame_with`](Self::render_frame_with), after the
+    /// closure returns). This replaces any previously registered hook.
+    ///
+    /// The hook runs inside `C3D_FrameEnd` *before* the frame's commands are
+    /// submitted to the GPU, so it is not a notification that rendering has
+    /// completed.
+    ///
+    /// Note that `hook` is called from C code, so panicking inside it will abort.
+    #[doc(alias = "C3D_FrameEndHook")]
+    pub fn set_frame_end_hook(&mut self, hook: impl FnMut() + 'static) {
+        // The old hook must be unregistered before the new one is registered,
+        // otherwise dropping it would unregister the new one instead.
+        self.frame_end_hook = None;
+        self.frame_end_hook = Some(FrameEndHook::new(hook));
+    }
+
+    /// Unregister the hook set by [`set_frame_end_hook`](Self::set_frame_end_hook),
+    /// if any.
+    pub fn clear_frame_end_hook(&mut self) {
+        self.frame_end_hook = None;
+    }
+
+    /// Get a handle for submitting GX commands, synchronized with the rendering
+    /// commands issued by `citro3d`.
+    pub fn gx_queue(&mut self) -> gx::Queue<'_> {
+        gx::Queue::new(self)
+    }
+
     /// Get the buffer info being used, if it exists. Note that the resulting
     /// [`buffer::Info`] is copied from the one currently in use.
     #[doc(alias = "C3D_GetBufInfo")]
@@ -200,6 +268,49 @@ impl Instance {
         }
     }
 
+    /// Render primitives from the current vertex array buffer, in the order
+    /// given by `indices`. Each index refers to a vertex within `vbo_data`.
+    ///
+    /// # Errors
+    ///
+    /// * if `indices` is not allocated with the [`ctru::linear`] allocator
+    /// * if any index is out of bounds for `vbo_data`
+    /// * if `indices` has too many elements to be drawn at once
+    #[doc(alias = "C3D_DrawElements")]
+    pub fn draw_elements<I: buffer::Index>(
+        &mut self,
+        primitive: buffer::Primitive,
+        vbo_data: buffer::Slice,
+        indices: &[I],
+    ) -> Result<()> {
+        // The GPU reads the indices directly, so they must be in linear memory
+        gx::check_gpu_accessible(indices)?;
+
+        // An out of bounds index would make the GPU read past the end of the VBO
+        let vbo_len = u32::try_from(vbo_data.len()).unwrap_or(0);
+        if !indices.iter().all(|&i| Into::<u32>::into(i) < vbo_len) {
+            return Err(Error::InvalidSize);
+        }
+
+        let count = indices.len().try_into()?;
+
+        self.set_buffer_info(vbo_data.info());
+
+        // TODO: like the VBO data in `draw_arrays`, the indices are only read by
+        // the GPU once the command buffer is flushed, so they really need to live
+        // at least until the end of the frame.
+        unsafe {
+            citro3d_sys::C3D_DrawElements(
+                primitive as ctru_sys::GPU_Primitive_t,
+                count,
+                I::RAW_TYPE,
+                indices.as_ptr().cast(),
+            );
+        }
+
+        Ok(())
+    }
+
     /// Use the given [`shader::Program`] for subsequent draw calls.
     pub fn bind_program(&mut self, program: &shader::Program) {
         // SAFETY: AFAICT C3D_BindProgram just copies pointers from the given program,
@@ -305,4 +416,66 @@ mod tests {
         drop(instance);
         drop(target);
     }
+
+    #[test]
+    fn draw_elements_checks_indices() {
+        let mut instance = Instance::new().unwrap();
+
+        let mut attr_info = attrib::Info::new();
+        let reg0 = attrib::Register::new(0).unwrap();
+        attr_info
+            .add_loader(reg0, attrib::Format::Float, 3)
+            .unwrap();
+
+        let mut vbo = Vec::with_capacity_in(3, ctru::linear::LinearAllocator);
+        vbo.extend_from_slice(&[[0.0_f32; 3]; 3]);
+
+        let mut buf_info = buffer::Info::new();
+        let vbo_data = buf_info.add(&vbo, &attr_info).unwrap();
+
+        let not_linear: Vec<u16> = vec![0, 1, 2];
+        assert!(matches!(
+            instance.draw_elements(buffer::Primitive::Triangles, vbo_data, &not_linear),
+            Err(Error::InvalidMemoryLocation)
+        ));
+
+        let mut out_of_bounds = Vec::with_capacity_in(3, ctru::linear::LinearAllocator);
+        out_of_bounds.extend_from_slice(&[0_u16, 1, 3]);
+        assert!(matches!(
+            instance.draw_elements(buffer::Primitive::Triangles, vbo_data, &out_of_bounds),
+            Err(Error::InvalidSize)
+        ));
+    }
+
+    #[test]
+    fn gx_memory_fill() {
+        let mut instance = Instance::new().unwrap();
+
+        // NOTE: emulators generally don't model the data cache, so this doesn't
+        // exercise the cache flush/invalidate that real hardware needs.
+        let mut buf = Vec::with_capacity_in(64, ctru::linear::LinearAllocator);
+        buf.resize(64, 0_u32);
+
+        instance
+            .gx_queue()
+            .memory_fill(&mut buf, 0xDEAD_BEEF, gx::FillWidth::Bits32)
+            .unwrap();
+        assert!(buf.iter().all(|&v| v == 0xDEAD_BEEF));
+
+        // Linear allocations are 8-byte aligned, so skipping one `u32` misaligns the start
+        assert!(matches!(
+            instance
+                .gx_queue()
+                .memory_fill(&mut buf[1..], 0, gx::FillWidth::Bits32),
+            Err(Error::InvalidAlignment)
+        ));
+
+        let mut not_linear = vec![0_u32; 64];
+        assert!(matches!(
+            instance
+                .gx_queue()
+                .memory_fill(&mut not_linear, 0, gx::FillWidth::Bits32),
+            Err(Error::InvalidMemoryLocation)
+        ));
+    }
 }
@@ -4,16 +4,20 @@
 // reimplementing some of those calls. Many of them are pretty trivial impls
 
 mod fvec;
+mod interpolate;
 mod matrix;
 mod ops;
 mod projection;
+mod quat;
 
 pub use fvec::{FVec, FVec3, FVec4};
+pub use interpolate::{Easing, Interpolate, Keyframe, Keyframes};
 pub use matrix::Matrix4;
 pub use projection::{
     AspectRatio, ClipPlanes, CoordinateOrientation, Orthographic, Perspective, Projection,
     ScreenOrientation, StereoDisplacement,
 };
+pub use quat::FQuat;
 
 /// A 4-vector of `u8`s.
 ///
@@ -45,11 +49,6 @@ impl IVec {
     }
 }
 
-/// A quaternion, internally represented the same way as [`FVec`].
-#[allow(dead_code)]
-#[doc(alias = "C3D_FQuat")]
-pub struct FQuat(citro3d_sys::C3D_FQuat);
-
 #[cfg(test)]
 mod tests {
     use super::IVec;
@@ -147,6 +147,22 @@ impl FVec4 {
     pub fn normalize(self) -> Self {
         Self(unsafe { citro3d_sys::FVec4_Normalize(self.0) })
     }
+
+    /// Linearly interpolate between two vectors. `t` is not clamped, so values
+    /// outside `0.0..=1.0` will extrapolate.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FVec4;
+    /// # use approx::assert_abs_diff_eq;
+    /// let l = FVec4::splat(1.0);
+    /// let r = FVec4::new(3.0, 5.0, 1.0, -1.0);
+    /// assert_abs_diff_eq!(l.lerp(r, 0.5), FVec4::new(2.0, 3.0, 1.0, 0.0));
+    /// ```
+    pub fn lerp(self, rhs: Self, t: f32) -> Self {
+        self + (rhs - self) * t
+    }
 }
 
 impl FVec3 {
@@ -253,6 +269,22 @@ impl FVec3 {
     pub fn normalize(self) -> Self {
         Self(unsafe { citro3d_sys::FVec3_Normalize(self.0) })
     }
+
+    /// Linearly interpolate between two vectors. `t` is not clamped, so values
+    /// outside `0.0..=1.0` will extrapolate.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FVec3;
+    /// # use approx::assert_abs_diff_eq;
+    /// let l = FVec3::new(0.0, 1.0, 2.0);
+    /// let r = FVec3::new(4.0, 1.0, -2.0);
+    /// assert_abs_diff_eq!(l.lerp(r, 0.25), FVec3::new(1.0, 1.0, 1.0));
+    /// ```
+    pub fn lerp(self, rhs: Self, t: f32) -> Self {
+        self + (rhs - self) * t
+    }
 }
 
 #[cfg(feature = "glam")]
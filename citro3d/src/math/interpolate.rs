@@ -0,0 +1,239 @@
+//! Interpolation helpers for animation, such as easing curves and keyframes.
+
+use super::{FQuat, FVec3, FVec4, Matrix4};
+use crate::{Error, Result};
+
+/// A value which can be smoothly interpolated between two endpoints.
+pub trait Interpolate: Copy {
+    /// Interpolate between `self` (at `t = 0.0`) and `other` (at `t = 1.0`).
+    fn interpolate(self, other: Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for FVec3 {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl Interpolate for FVec4 {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl Interpolate for Matrix4 {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl Interpolate for FQuat {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self.slerp(other, t)
+    }
+}
+
+/// An easing curve, used to remap the progress of an animation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Quadratic, starting slow and speeding up.
+    QuadIn,
+    /// Quadratic, starting fast and slowing down.
+    QuadOut,
+    /// Quadratic, slow at both ends.
+    QuadInOut,
+    /// Cubic, starting slow and speeding up.
+    CubicIn,
+    /// Cubic, starting fast and slowing down.
+    CubicOut,
+    /// Cubic, slow at both ends.
+    CubicInOut,
+    /// Hermite interpolation, like GLSL's `smoothstep`.
+    SmoothStep,
+}
+
+impl Easing {
+    /// Apply the easing curve to `t`, which is first clamped to `0.0..=1.0`.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::Easing;
+    /// # use approx::assert_abs_diff_eq;
+    /// assert_abs_diff_eq!(Easing::QuadIn.apply(0.5), 0.25);
+    /// assert_abs_diff_eq!(Easing::QuadOut.apply(0.5), 0.75);
+    /// assert_abs_diff_eq!(Easing::Linear.apply(2.0), 1.0);
+    /// ```
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::QuadIn => t * t,
+            Self::QuadOut => t * (2.0 - t),
+            Self::QuadInOut if t < 0.5 => 2.0 * t * t,
+            Self::QuadInOut => -1.0 + (4.0 - 2.0 * t) * t,
+            Self::CubicIn => t * t * t,
+            Self::CubicOut => (t - 1.0).powi(3) + 1.0,
+            Self::CubicInOut if t < 0.5 => 4.0 * t * t * t,
+            Self::CubicInOut => (2.0 * t - 2.0).powi(3) / 2.0 + 1.0,
+            Self::SmoothStep => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A value at a given point in time, used to build a [`Keyframes`] animation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe<T> {
+    /// The time of this keyframe, in whatever unit the animation is sampled with.
+    pub time: f32,
+    /// The value at this keyframe.
+    pub value: T,
+    /// The easing used when interpolating from this keyframe to the next one.
+    pub easing: Easing,
+}
+
+impl<T> Keyframe<T> {
+    /// Create a keyframe with [linear](Easing::Linear) easing.
+    pub fn new(time: f32, value: T) -> Self {
+        Self {
+            time,
+            value,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Set the easing used when interpolating from this keyframe to the next one.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// A sequence of [`Keyframe`]s which can be sampled at any point in time.
+#[derive(Debug, Clone)]
+pub struct Keyframes<T> {
+    frames: Vec<Keyframe<T>>,
+}
+
+impl<T: Interpolate> Keyframes<T> {
+    /// Create an animation from the given keyframes, which are sorted by time.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any keyframe's time is NaN or infinite.
+    pub fn new(frames: impl IntoIterator<Item = Keyframe<T>>) -> Result<Self> {
+        let mut frames: Vec<_> = frames.into_iter().collect();
+        if !frames.iter().all(|frame| frame.time.is_finite()) {
+            return Err(Error::InvalidValue);
+        }
+
+        frames.sort_by(|l, r| l.time.total_cmp(&r.time));
+        Ok(Self { frames })
+    }
+
+    /// The time between the first and last keyframes.
+    pub fn duration(&self) -> f32 {
+        match (self.frames.first(), self.frames.last()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.0,
+        }
+    }
+
+    /// Sample the animation at `time`. Times before the first keyframe or
+    /// after the last keyframe are clamped to those keyframes' values, and a NaN
+    /// `time` is treated as before the first keyframe.
+    ///
+    /// Returns [`None`] if there are no keyframes.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{Keyframe, Keyframes};
+    /// # use approx::assert_abs_diff_eq;
+    /// let anim = Keyframes::new([Keyframe::new(0.0, 1.0), Keyframe::new(2.0, 3.0)]).unwrap();
+    /// assert_abs_diff_eq!(anim.sample(1.0).unwrap(), 2.0);
+    /// assert_abs_diff_eq!(anim.sample(5.0).unwrap(), 3.0);
+    /// ```
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let first = self.frames.first()?;
+        let next_idx = self.frames.partition_point(|frame| frame.time <= time);
+        if next_idx == 0 {
+            return Some(first.value);
+        }
+
+        let prev = &self.frames[next_idx - 1];
+        let Some(next) = self.frames.get(next_idx) else {
+            return Some(prev.value);
+        };
+
+        // prev.time <= time < next.time, so this can't divide by zero
+        let t = (time - prev.time) / (next.time - prev.time);
+        Some(prev.value.interpolate(next.value, prev.easing.apply(t)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn easing_endpoints() {
+        let easings = [
+            Easing::Linear,
+            Easing::QuadIn,
+            Easing::QuadOut,
+            Easing::QuadInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::SmoothStep,
+        ];
+
+        for easing in easings {
+            assert_abs_diff_eq!(easing.apply(0.0), 0.0);
+            assert_abs_diff_eq!(easing.apply(1.0), 1.0);
+        }
+
+        assert_abs_diff_eq!(Easing::QuadInOut.apply(0.5), 0.5);
+        assert_abs_diff_eq!(Easing::CubicInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn keyframes() {
+        let anim = Keyframes::new([
+            Keyframe::new(2.0, FVec3::splat(4.0)).with_easing(Easing::QuadIn),
+            Keyframe::new(0.0, FVec3::splat(0.0)),
+            Keyframe::new(1.0, FVec3::splat(2.0)),
+            Keyframe::new(3.0, FVec3::splat(8.0)),
+        ])
+        .unwrap();
+
+        assert_abs_diff_eq!(anim.duration(), 3.0);
+        assert_abs_diff_eq!(anim.sample(-1.0).unwrap(), FVec3::splat(0.0));
+        assert_abs_diff_eq!(anim.sample(0.5).unwrap(), FVec3::splat(1.0));
+        assert_abs_diff_eq!(anim.sample(1.0).unwrap(), FVec3::splat(2.0));
+        assert_abs_diff_eq!(anim.sample(2.5).unwrap(), FVec3::splat(5.0));
+        assert_abs_diff_eq!(anim.sample(4.0).unwrap(), FVec3::splat(8.0));
+
+        assert!(Keyframes::<f32>::new([]).unwrap().sample(0.0).is_none());
+    }
+
+    #[test]
+    fn keyframes_non_finite() {
+        for time in [f32::NAN, -f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let frames = [Keyframe::new(0.0, 0.0), Keyframe::new(time, 1.0)];
+            assert!(matches!(Keyframes::new(frames), Err(Error::InvalidValue)));
+        }
+    }
+}
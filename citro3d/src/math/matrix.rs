@@ -135,6 +135,61 @@ impl Matrix4 {
         }
     }
 
+    /// Compute the matrix for transforming normal vectors by this model(view)
+    /// matrix, i.e. the inverse-transpose of its upper 3x3. The translation
+    /// part of the result is cleared.
+    ///
+    /// For rigid transforms (rotation and translation only) or uniform scaling,
+    /// the upper 3x3 of the original matrix can be used directly, but non-uniform
+    /// scaling will skew normals unless this matrix is used instead.
+    ///
+    /// # Errors
+    ///
+    /// If the upper 3x3 has no inverse, the matrix will be returned unchanged as an [`Err`].
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::Matrix4;
+    /// # use approx::assert_abs_diff_eq;
+    /// let mut model = Matrix4::identity();
+    /// model.translate(1.0, 2.0, 3.0);
+    /// model.scale(2.0, 1.0, 4.0);
+    ///
+    /// let normal = model.normal_matrix().unwrap();
+    /// assert_abs_diff_eq!(normal, Matrix4::diagonal(0.5, 1.0, 0.25, 1.0));
+    /// ```
+    pub fn normal_matrix(self) -> Result<Self, Self> {
+        let [r0, r1, r2, _] = self
+            .rows_xyzw()
+            .map(|[x, y, z, _]| FVec4::new(x, y, z, 0.0));
+        let upper = Self::from_rows([r0, r1, r2, FVec4::new(0.0, 0.0, 0.0, 1.0)]);
+        upper.inverse().map(Self::transpose).map_err(|_| self)
+    }
+
+    /// Linearly interpolate each cell of two matrices. Note that this does not
+    /// preserve rotations, so for animating a transform it is usually better to
+    /// interpolate its components (e.g. rotations with
+    /// [`FQuat::slerp`](super::FQuat::slerp)) and rebuild the matrix. An
+    /// [`FQuat`](super::FQuat) can be converted to a rotation matrix with [`From`].
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::Matrix4;
+    /// # use approx::assert_abs_diff_eq;
+    /// let l = Matrix4::identity();
+    /// let r = Matrix4::diagonal(3.0, 3.0, 3.0, 3.0);
+    /// assert_abs_diff_eq!(l.lerp(r, 0.5), Matrix4::diagonal(2.0, 2.0, 2.0, 2.0));
+    /// ```
+    pub fn lerp(self, rhs: Self, t: f32) -> Self {
+        let mut rows = self.rows_wzyx();
+        for (row, rhs_row) in rows.iter_mut().zip(rhs.rows_wzyx()) {
+            *row = row.lerp(rhs_row, t);
+        }
+        Self::from_rows(rows)
+    }
+
     /// Construct the identity matrix.
     #[doc(alias = "Mtx_Identity")]
     pub fn identity() -> Self {
@@ -0,0 +1,255 @@
+//! Quaternions, used to represent rotations.
+
+use std::fmt;
+use std::mem::MaybeUninit;
+
+#[cfg(feature = "approx")]
+use approx::AbsDiffEq;
+
+use super::{FVec3, FVec4, Matrix4};
+
+/// A quaternion, internally represented the same way as [`FVec`](super::FVec).
+///
+/// # Layout
+/// Like [`FVec4`], this uses the PICA layout (RKJI in memory).
+#[doc(alias = "C3D_FQuat")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct FQuat(pub(crate) citro3d_sys::C3D_FQuat);
+
+impl fmt::Debug for FQuat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FQuat")
+            .field("i", &self.i())
+            .field("j", &self.j())
+            .field("k", &self.k())
+            .field("r", &self.r())
+            .finish()
+    }
+}
+
+impl FQuat {
+    /// Create a new [`FQuat`] from its components.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FQuat;
+    /// let q = FQuat::new(0.0, 0.0, 0.0, 1.0);
+    /// ```
+    #[doc(alias = "Quat_New")]
+    pub fn new(i: f32, j: f32, k: f32, r: f32) -> Self {
+        Self(unsafe { citro3d_sys::FVec4_New(i, j, k, r) })
+    }
+
+    /// The identity quaternion, i.e. no rotation.
+    #[doc(alias = "Quat_Identity")]
+    pub fn identity() -> Self {
+        Self::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Create a quaternion representing a rotation of `angle` radians around `axis`.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::{FQuat, FVec3};
+    /// # use approx::assert_abs_diff_eq;
+    /// let q = FQuat::from_axis_angle(FVec3::new(0.0, 0.0, 1.0), 90.0_f32.to_radians());
+    /// let (sin, cos) = 45.0_f32.to_radians().sin_cos();
+    /// assert_abs_diff_eq!(q, FQuat::new(0.0, 0.0, sin, cos));
+    /// ```
+    #[doc(alias = "Quat_FromAxisAngle")]
+    pub fn from_axis_angle(axis: FVec3, angle: f32) -> Self {
+        Self(unsafe { citro3d_sys::Quat_FromAxisAngle(axis.0, angle) })
+    }
+
+    /// Wrap a raw [`citro3d_sys::C3D_FQuat`]
+    pub fn from_raw(raw: citro3d_sys::C3D_FQuat) -> Self {
+        Self(raw)
+    }
+
+    /// The quaternion's `i` component.
+    pub fn i(self) -> f32 {
+        self.as_fvec().x()
+    }
+
+    /// The quaternion's `j` component.
+    pub fn j(self) -> f32 {
+        self.as_fvec().y()
+    }
+
+    /// The quaternion's `k` component.
+    pub fn k(self) -> f32 {
+        self.as_fvec().z()
+    }
+
+    /// The quaternion's real component.
+    pub fn r(self) -> f32 {
+        self.as_fvec().w()
+    }
+
+    /// The dot product of two quaternions.
+    #[doc(alias = "Quat_Dot")]
+    pub fn dot(self, rhs: Self) -> f32 {
+        self.as_fvec().dot(rhs.as_fvec())
+    }
+
+    /// Normalize the quaternion to a magnitude of `1.0`.
+    #[doc(alias = "Quat_Normalize")]
+    pub fn normalize(self) -> Self {
+        Self::from_fvec(self.as_fvec().normalize())
+    }
+
+    /// Normalized linear interpolation between two rotations. This is cheaper
+    /// than [`slerp`](Self::slerp) but does not interpolate at a constant
+    /// angular velocity. Always takes the shortest path between `self` and `rhs`.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FQuat;
+    /// # use approx::assert_abs_diff_eq;
+    /// let a = FQuat::identity();
+    /// let b = FQuat::new(0.0, 0.0, 1.0, 0.0);
+    /// let half = 0.5_f32.sqrt();
+    /// assert_abs_diff_eq!(a.nlerp(b, 0.5), FQuat::new(0.0, 0.0, half, half));
+    /// ```
+    pub fn nlerp(self, rhs: Self, t: f32) -> Self {
+        let (lhs, rhs) = (self.as_fvec(), self.shortest_path_to(rhs).as_fvec());
+        Self::from_fvec(lhs.lerp(rhs, t).normalize())
+    }
+
+    /// Spherical linear interpolation between two rotations, at a constant
+    /// angular velocity. Always takes the shortest path between `self` and `rhs`.
+    ///
+    /// # Example
+    /// ```
+    /// # let _runner = test_runner::GdbRunner::default();
+    /// # use citro3d::math::FQuat;
+    /// # use approx::assert_abs_diff_eq;
+    /// let a = FQuat::identity();
+    /// let b = FQuat::new(0.0, 0.0, 1.0, 0.0);
+    /// let (sin, cos) = 45.0_f32.to_radians().sin_cos();
+    /// assert_abs_diff_eq!(a.slerp(b, 0.5), FQuat::new(0.0, 0.0, sin, cos));
+    /// ```
+    pub fn slerp(self, rhs: Self, t: f32) -> Self {
+        // Past this point the angle is small enough that nlerp is indistinguishable,
+        // and dividing by sin(theta) becomes numerically unstable.
+        const NLERP_THRESHOLD: f32 = 0.9995;
+
+        let rhs = self.shortest_path_to(rhs);
+        let cos_theta = self.dot(rhs);
+        if cos_theta > NLERP_THRESHOLD {
+            return self.nlerp(rhs, t);
+        }
+
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+        let lhs_weight = ((1.0 - t) * theta).sin() / sin_theta;
+        let rhs_weight = (t * theta).sin() / sin_theta;
+
+        Self::from_fvec(self.as_fvec() * lhs_weight + rhs.as_fvec() * rhs_weight)
+    }
+
+    /// `q` and `-q` represent the same rotation, so pick whichever one is closer
+    /// to `self` to avoid interpolating the "long way around".
+    fn shortest_path_to(self, rhs: Self) -> Self {
+        if self.dot(rhs) < 0.0 {
+            Self::from_fvec(-rhs.as_fvec())
+        } else {
+            rhs
+        }
+    }
+
+    fn as_fvec(self) -> FVec4 {
+        FVec4::from_raw(self.0)
+    }
+
+    fn from_fvec(v: FVec4) -> Self {
+        Self(v.0)
+    }
+}
+
+impl From<FQuat> for Matrix4 {
+    #[doc(alias = "Mtx_FromQuat")]
+    fn from(q: FQuat) -> Self {
+        let mut out = MaybeUninit::uninit();
+        unsafe {
+            citro3d_sys::Mtx_FromQuat(out.as_mut_ptr(), q.0);
+            Matrix4::from_raw(out.assume_init())
+        }
+    }
+}
+
+impl PartialEq for FQuat {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_fvec() == other.as_fvec()
+    }
+}
+
+#[cfg(feature = "approx")]
+impl AbsDiffEq for FQuat {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> Self::Epsilon {
+        FVec4::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.as_fvec().abs_diff_eq(&other.as_fvec(), epsilon)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Quat> for FQuat {
+    fn from(value: glam::Quat) -> Self {
+        Self::new(value.x, value.y, value.z, value.w)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<FQuat> for glam::Quat {
+    fn from(value: FQuat) -> Self {
+        glam::Quat::from_xyzw(value.i(), value.j(), value.k(), value.r())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn fquat() {
+        let q = FQuat::new(1.0, 2.0, 3.0, 4.0);
+        let actual = [q.i(), q.j(), q.k(), q.r()];
+        let expected = [1.0, 2.0, 3.0, 4.0];
+        assert_abs_diff_eq!(&actual[..], &expected[..]);
+    }
+
+    #[test]
+    fn slerp_shortest_path() {
+        let (sin, cos) = 45.0_f32.to_radians().sin_cos();
+        let a = FQuat::identity();
+        let b = FQuat::new(0.0, 0.0, -sin, -cos);
+
+        // -b is the same rotation as b but closer to a, so interpolation should end at -b
+        let expected = FQuat::new(0.0, 0.0, sin, cos);
+        assert_abs_diff_eq!(a.slerp(b, 0.0), a);
+        assert_abs_diff_eq!(a.slerp(b, 1.0), expected);
+        assert_abs_diff_eq!(a.nlerp(b, 1.0), expected);
+    }
+
+    #[test]
+    fn matrix_from_quat() {
+        let angle = 30.0_f32.to_radians();
+        let q = FQuat::from_axis_angle(FVec3::new(0.0, 1.0, 0.0), angle);
+
+        let mut expected = Matrix4::identity();
+        expected.rotate_y(angle);
+        assert_abs_diff_eq!(Matrix4::from(q), expected);
+        assert_abs_diff_eq!(Matrix4::from(FQuat::identity()), Matrix4::identity());
+    }
+}
@@ -1,10 +1,16 @@
-use citro3d_sys::{GX_TRANSFER_IN_FORMAT, GX_TRANSFER_OUT_FORMAT};
-use ctru_sys::GX_TRANSFER_FORMAT;
+//! Flags and formats for GX data transfers, such as copying a rendered frame
+//! to the screen.
+
+use citro3d_sys::{
+    GX_TRANSFER_FLIP_VERT, GX_TRANSFER_IN_FORMAT, GX_TRANSFER_OUT_FORMAT, GX_TRANSFER_OUT_TILED,
+    GX_TRANSFER_RAW_COPY, GX_TRANSFER_SCALING,
+};
+use ctru_sys::{GX_TRANSFER_FORMAT, GX_TRANSFER_SCALE};
 
 use super::ColorFormat;
 
 /// Control flags for a GX data transfer.
-#[derive(Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct Flags(u32);
 
 impl Flags {
@@ -20,6 +26,32 @@ impl Flags {
         Self(self.0 | GX_TRANSFER_OUT_FORMAT(fmt as GX_TRANSFER_FORMAT))
     }
 
+    /// Flip the data vertically while transferring it.
+    #[must_use]
+    pub fn flip_vertical(self, flip: bool) -> Self {
+        Self(self.0 | GX_TRANSFER_FLIP_VERT(flip))
+    }
+
+    /// Convert linear input data to the GPU's tiled format, instead of the
+    /// default of converting tiled input to linear output.
+    #[must_use]
+    pub fn out_tiled(self, tiled: bool) -> Self {
+        Self(self.0 | GX_TRANSFER_OUT_TILED(tiled))
+    }
+
+    /// Copy the data as-is, without any tiling or format conversion.
+    #[must_use]
+    pub fn raw_copy(self, raw_copy: bool) -> Self {
+        Self(self.0 | GX_TRANSFER_RAW_COPY(raw_copy))
+    }
+
+    /// Downscale the data while transferring it.
+    #[must_use]
+    pub fn scaling(self, scale: Scale) -> Self {
+        Self(self.0 | GX_TRANSFER_SCALING(scale as GX_TRANSFER_SCALE))
+    }
+
+    /// Get the raw flags value, as passed to `GX_DisplayTransfer`.
     #[must_use]
     pub fn bits(self) -> u32 {
         self.0
@@ -32,6 +64,7 @@ impl Flags {
 /// convertible to one another. Use [`From::from`] to get the [`Format`] corresponding
 /// to a given [`ColorFormat`].
 #[repr(u8)]
+#[derive(Debug, Clone, Copy)]
 #[doc(alias = "GX_TRANSFER_FORMAT")]
 pub enum Format {
     /// 8-bit Red + 8-bit Green + 8-bit Blue + 8-bit Alpha.
@@ -46,6 +79,19 @@ pub enum Format {
     RGBA4 = ctru_sys::GX_TRANSFER_FMT_RGBA4,
 }
 
+/// The downscaling to apply during a data transfer.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+#[doc(alias = "GX_TRANSFER_SCALE")]
+pub enum Scale {
+    /// Don't scale the data.
+    None = ctru_sys::GX_TRANSFER_SCALE_NO,
+    /// Halve the width of the data (2x1 downscale).
+    X = ctru_sys::GX_TRANSFER_SCALE_X,
+    /// Halve both the width and height of the data (2x2 downscale).
+    XY = ctru_sys::GX_TRANSFER_SCALE_XY,
+}
+
 impl From<ColorFormat> for Format {
     fn from(color_fmt: ColorFormat) -> Self {
         match color_fmt {
@@ -0,0 +1,333 @@
+//! Generate terrain meshes from heightmaps.
+//!
+//! A [`Heightmap`] is turned into one or more [`Chunk`]s, each of which is an
+//! indexed triangle mesh stored in linear memory, ready to be registered with
+//! [`buffer::Info::add`](crate::buffer::Info::add) and drawn with
+//! [`Instance::draw_elements`](crate::Instance::draw_elements).
+
+use ctru::linear::LinearAllocator;
+
+use crate::attrib;
+use crate::math::FVec3;
+use crate::{Error, Result};
+
+/// A single terrain vertex, laid out for use as a vertex buffer object.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vertex {
+    /// The `xyz` position of the vertex.
+    pub position: [f32; 3],
+    /// The `xyz` unit normal of the terrain surface at this vertex.
+    pub normal: [f32; 3],
+    /// The `uv` texture coordinates, spanning `0.0..=1.0` across the whole heightmap.
+    pub uv: [f32; 2],
+}
+
+impl Vertex {
+    /// Build the [`attrib::Info`] describing a buffer of terrain vertices,
+    /// with each attribute loaded into the given shader input register.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the attributes cannot be registered.
+    pub fn attrib_info(
+        position: attrib::Register,
+        normal: attrib::Register,
+        uv: attrib::Register,
+    ) -> Result<attrib::Info> {
+        let mut info = attrib::Info::new();
+        info.add_loader(position, attrib::Format::Float, 3)?;
+        info.add_loader(normal, attrib::Format::Float, 3)?;
+        info.add_loader(uv, attrib::Format::Float, 2)?;
+        Ok(info)
+    }
+}
+
+/// How heightmap samples are mapped into world space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale {
+    /// The distance between adjacent samples along the X and Z axes.
+    pub cell_size: f32,
+    /// The multiplier applied to each sample to get its Y coordinate.
+    pub height: f32,
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self {
+            cell_size: 1.0,
+            height: 1.0,
+        }
+    }
+}
+
+/// A grid of height samples. Samples are stored row by row, with rows running
+/// along the X axis and successive rows along the Z axis.
+#[derive(Debug, Clone)]
+pub struct Heightmap {
+    width: usize,
+    depth: usize,
+    heights: Vec<f32>,
+}
+
+/// A section of terrain mesh, along with its bounding box for culling.
+#[derive(Debug)]
+pub struct Chunk {
+    vertices: Vec<Vertex, LinearAllocator>,
+    indices: Vec<u16, LinearAllocator>,
+    min: FVec3,
+    max: FVec3,
+}
+
+impl Heightmap {
+    /// Create a heightmap from `width * depth` samples.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `width` or `depth` is less than 2, or if the number of samples
+    /// doesn't match the dimensions.
+    pub fn new(width: usize, depth: usize, heights: Vec<f32>) -> Result<Self> {
+        if width < 2 || depth < 2 || width.checked_mul(depth) != Some(heights.len()) {
+            return Err(Error::InvalidSize);
+        }
+
+        Ok(Self {
+            width,
+            depth,
+            heights,
+        })
+    }
+
+    /// Create a heightmap from 8-bit grayscale image data, mapping each
+    /// pixel from `0..=255` to a height in `0.0..=1.0`.
+    ///
+    /// # Errors
+    ///
+    /// Fails under the same conditions as [`Heightmap::new`].
+    pub fn from_luma8(width: usize, depth: usize, pixels: &[u8]) -> Result<Self> {
+        let heights = pixels.iter().map(|&p| f32::from(p) / 255.0).collect();
+        Self::new(width, depth, heights)
+    }
+
+    /// The number of samples along the X axis.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of samples along the Z axis.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Get the sample at the given grid coordinates, if it exists.
+    pub fn height(&self, x: usize, z: usize) -> Option<f32> {
+        if x < self.width && z < self.depth {
+            Some(self.heights[z * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Build a single mesh for the whole heightmap.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the heightmap has more vertices than can be addressed with
+    /// 16-bit indices. Use [`Heightmap::chunks`] to split up larger heightmaps.
+    pub fn mesh(&self, scale: Scale) -> Result<Chunk> {
+        self.chunk(0, 0, self.width, self.depth, scale)
+    }
+
+    /// Split the heightmap into chunks of up to `chunk_cells * chunk_cells` grid
+    /// cells each. Adjacent chunks share their border vertices, so there are no
+    /// seams between them.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `chunk_cells` is zero, or too large for a chunk's vertices to be
+    /// addressed with 16-bit indices.
+    pub fn chunks(&self, scale: Scale, chunk_cells: usize) -> Result<Vec<Chunk>> {
+        if chunk_cells == 0 {
+            return Err(Error::InvalidSize);
+        }
+
+        let mut chunks = Vec::new();
+        for z in (0..self.depth - 1).step_by(chunk_cells) {
+            for x in (0..self.width - 1).step_by(chunk_cells) {
+                // Saturate so oversized chunks just cover the rest of the heightmap
+                let x_end = x.saturating_add(chunk_cells).saturating_add(1);
+                let z_end = z.saturating_add(chunk_cells).saturating_add(1);
+                let (x_end, z_end) = (x_end.min(self.width), z_end.min(self.depth));
+                chunks.push(self.chunk(x, z, x_end, z_end, scale)?);
+            }
+        }
+
+        Ok(chunks)
+    }
+
+    /// Build the mesh for samples in `x_start..x_end` and `z_start..z_end`.
+    fn chunk(
+        &self,
+        x_start: usize,
+        z_start: usize,
+        x_end: usize,
+        z_end: usize,
+        scale: Scale,
+    ) -> Result<Chunk> {
+        let row_len = x_end - x_start;
+        let rows = z_end - z_start;
+
+        // Make sure the largest index fits before building anything
+        u16::try_from(row_len * rows - 1)?;
+
+        let mut vertices = Vec::with_capacity_in(row_len * rows, LinearAllocator);
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+
+        for z in z_start..z_end {
+            for x in x_start..x_end {
+                let vertex = self.vertex(x, z, scale);
+                for ((lo, hi), pos) in min.iter_mut().zip(&mut max).zip(vertex.position) {
+                    *lo = lo.min(pos);
+                    *hi = hi.max(pos);
+                }
+                vertices.push(vertex);
+            }
+        }
+
+        let mut indices = Vec::with_capacity_in((row_len - 1) * (rows - 1) * 6, LinearAllocator);
+        for z in 0..rows - 1 {
+            for x in 0..row_len - 1 {
+                // These can't overflow since we checked the largest index above
+                let top_left = (z * row_len + x) as u16;
+                let top_right = top_left + 1;
+                let bottom_left = top_left + row_len as u16;
+                let bottom_right = bottom_left + 1;
+
+                // Counter-clockwise when viewed from above (+Y)
+                indices.extend_from_slice(&[
+                    top_left,
+                    bottom_left,
+                    top_right,
+                    top_right,
+                    bottom_left,
+                    bottom_right,
+                ]);
+            }
+        }
+
+        Ok(Chunk {
+            vertices,
+            indices,
+            min: FVec3::new(min[0], min[1], min[2]),
+            max: FVec3::new(max[0], max[1], max[2]),
+        })
+    }
+
+    fn vertex(&self, x: usize, z: usize, scale: Scale) -> Vertex {
+        let sample = |x: usize, z: usize| self.heights[z * self.width + x] * scale.height;
+
+        // Central differences, falling back to one-sided differences at the
+        // edges. These use the whole heightmap so chunk borders stay seamless.
+        let (left, right) = (x.saturating_sub(1), (x + 1).min(self.width - 1));
+        let (back, front) = (z.saturating_sub(1), (z + 1).min(self.depth - 1));
+        let dx = (sample(right, z) - sample(left, z)) / ((right - left) as f32 * scale.cell_size);
+        let dz = (sample(x, front) - sample(x, back)) / ((front - back) as f32 * scale.cell_size);
+
+        let len = (dx * dx + 1.0 + dz * dz).sqrt();
+
+        Vertex {
+            position: [
+                x as f32 * scale.cell_size,
+                sample(x, z),
+                z as f32 * scale.cell_size,
+            ],
+            normal: [-dx / len, 1.0 / len, -dz / len],
+            uv: [
+                x as f32 / (self.width - 1) as f32,
+                z as f32 / (self.depth - 1) as f32,
+            ],
+        }
+    }
+}
+
+impl Chunk {
+    /// The vertex data of this chunk, to be registered as a VBO.
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    /// The indices into [`Chunk::vertices`], describing a list of
+    /// [triangles](crate::buffer::Primitive::Triangles).
+    pub fn indices(&self) -> &[u16] {
+        &self.indices
+    }
+
+    /// The minimum corner of this chunk's axis-aligned bounding box.
+    pub fn min(&self) -> FVec3 {
+        self.min
+    }
+
+    /// The maximum corner of this chunk's axis-aligned bounding box.
+    pub fn max(&self) -> FVec3 {
+        self.max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn invalid_size() {
+        assert!(Heightmap::new(1, 2, vec![0.0; 2]).is_err());
+        assert!(Heightmap::new(2, 2, vec![0.0; 3]).is_err());
+        assert!(Heightmap::from_luma8(2, 2, &[0; 4]).is_ok());
+    }
+
+    #[test]
+    fn flat_mesh() {
+        let map = Heightmap::new(3, 3, vec![0.5; 9]).unwrap();
+        let mesh = map.mesh(Scale::default()).unwrap();
+
+        assert_eq!(mesh.vertices().len(), 9);
+        assert_eq!(mesh.indices().len(), 2 * 2 * 6);
+        assert_eq!(&mesh.indices()[..6], &[0, 3, 1, 1, 3, 4]);
+
+        for vertex in mesh.vertices() {
+            assert_abs_diff_eq!(&vertex.normal[..], &[0.0, 1.0, 0.0][..]);
+        }
+
+        assert_abs_diff_eq!(mesh.min(), FVec3::new(0.0, 0.5, 0.0));
+        assert_abs_diff_eq!(mesh.max(), FVec3::new(2.0, 0.5, 2.0));
+    }
+
+    #[test]
+    fn chunks_share_borders() {
+        let map = Heightmap::new(5, 4, (0..20).map(|h| h as f32).collect()).unwrap();
+        let chunks = map.chunks(Scale::default(), 2).unwrap();
+
+        // 4x3 cells split into 2x2 chunks
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[0].vertices().len(), 9);
+        assert_eq!(chunks[3].vertices().len(), 6);
+        assert_eq!(chunks[0].vertices()[2], chunks[1].vertices()[0]);
+    }
+
+    #[test]
+    fn oversized_chunks() {
+        let small = Heightmap::new(3, 3, vec![0.0; 9]).unwrap();
+        let chunks = small.chunks(Scale::default(), usize::MAX).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].vertices().len(), 9);
+
+        // 300 * 300 vertices can't be addressed with 16-bit indices
+        let large = Heightmap::new(300, 300, vec![0.0; 300 * 300]).unwrap();
+        assert!(matches!(
+            large.chunks(Scale::default(), usize::MAX),
+            Err(Error::InvalidSize)
+        ));
+    }
+}